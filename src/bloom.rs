@@ -22,8 +22,9 @@
 //! double hashing.
 
 use bit_vec::BitVec;
-use fasthash::RandomState;
 use fasthash::{murmur3, xx};
+use std::convert::TryInto;
+use std::fmt;
 use std::hash::{BuildHasher, Hash, Hasher};
 
 const LN_SQR: f64 = core::f64::consts::LN_2 * core::f64::consts::LN_2;
@@ -33,17 +34,157 @@ const UNSET_BIT: bool = false;
 /// The default false positive probability value which is 1%.
 pub const DEFAULT_FALSE_POS: f64 = 0.01;
 
+/// Magic bytes identifying a serialized BloomFilter, written at the start of
+/// every `to_bytes` payload.
+const SERIALIZE_MAGIC: [u8; 4] = *b"RSBF";
+
+/// The current `to_bytes`/`from_bytes` wire format version. Bump this and
+/// branch on it in `from_bytes` if the layout ever needs to change, so older
+/// payloads remain readable.
+const SERIALIZE_VERSION: u8 = 1;
+
+/// The fixed-size portion of the serialized format: magic, version, seed,
+/// num_hashes, set_bits, and bit length, in that order.
+const SERIALIZE_HEADER_LEN: usize = 4 + 1 + 8 + 8 + 8 + 8;
+
+/// An error returned by `BloomFilter::from_bytes` when a byte slice cannot be
+/// parsed as a serialized Bloom filter.
+#[derive(Debug, PartialEq, Eq)]
+pub enum DeserializeError {
+  /// The byte slice is shorter than the fixed-size header, or too short to
+  /// hold the bit vector whose length the header records.
+  UnexpectedEof,
+  /// The byte slice does not start with the expected magic bytes.
+  InvalidMagic,
+  /// The byte slice was written by a version of this crate that uses a wire
+  /// format this build does not understand.
+  UnsupportedVersion(u8),
+}
+
+impl fmt::Display for DeserializeError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      DeserializeError::UnexpectedEof => write!(f, "unexpected end of input"),
+      DeserializeError::InvalidMagic => write!(f, "input is not a serialized BloomFilter"),
+      DeserializeError::UnsupportedVersion(v) => {
+        write!(f, "unsupported serialization version: {}", v)
+      }
+    }
+  }
+}
+
+impl std::error::Error for DeserializeError {}
+
+/// An error returned by `BloomFilter::union`/`BloomFilter::intersect` when the
+/// two filters do not share the same bit length, number of hashes, and seed.
+/// Combining filters with mismatched parameters would silently break their
+/// membership guarantees, so the operation is rejected instead.
+#[derive(Debug, PartialEq, Eq)]
+pub struct IncompatibleFilterError;
+
+impl fmt::Display for IncompatibleFilterError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(
+      f,
+      "bloom filters must share the same bit length, number of hashes, and seed to be combined"
+    )
+  }
+}
+
+impl std::error::Error for IncompatibleFilterError {}
+
+/// A `Hasher` that buffers the bytes written to it and digests them with
+/// Murmur3 128-bit on `finish`, seeded with a full 64 bits of entropy.
+///
+/// Murmur3's native seed parameter is only 32 bits wide, so the upper 32 bits
+/// of `seed` are mixed into the buffer itself rather than discarded, keeping
+/// the effective seed space the full `u64` that callers provide.
+#[derive(Clone)]
+pub struct Murmur3Hasher {
+  seed: u64,
+  buf: Vec<u8>,
+}
+
+impl Hasher for Murmur3Hasher {
+  fn write(&mut self, bytes: &[u8]) {
+    self.buf.extend_from_slice(bytes);
+  }
+
+  fn finish(&self) -> u64 {
+    let mut seeded = Vec::with_capacity(8 + self.buf.len());
+    seeded.extend_from_slice(&self.seed.to_le_bytes());
+    seeded.extend_from_slice(&self.buf);
+    murmur3::hash128_with_seed(&seeded, self.seed as u32) as u64
+  }
+}
+
+/// A `BuildHasher` that produces `Murmur3Hasher`s fixed to a given seed, so
+/// repeated calls to `build_hasher` hash identically.
+#[derive(Clone)]
+pub struct MurmurBuildHasher {
+  seed: u64,
+}
+
+impl BuildHasher for MurmurBuildHasher {
+  type Hasher = Murmur3Hasher;
+
+  fn build_hasher(&self) -> Murmur3Hasher {
+    Murmur3Hasher {
+      seed: self.seed,
+      buf: Vec::new(),
+    }
+  }
+}
+
+/// A `Hasher` that buffers the bytes written to it and digests them with
+/// xxHash 64-bit on `finish`, seeded with the full 64-bit seed natively.
+#[derive(Clone)]
+pub struct XxHasher {
+  seed: u64,
+  buf: Vec<u8>,
+}
+
+impl Hasher for XxHasher {
+  fn write(&mut self, bytes: &[u8]) {
+    self.buf.extend_from_slice(bytes);
+  }
+
+  fn finish(&self) -> u64 {
+    xx::hash64_with_seed(&self.buf, self.seed)
+  }
+}
+
+/// A `BuildHasher` that produces `XxHasher`s fixed to a given seed, so
+/// repeated calls to `build_hasher` hash identically.
+#[derive(Clone)]
+pub struct XxBuildHasher {
+  seed: u64,
+}
+
+impl BuildHasher for XxBuildHasher {
+  type Hasher = XxHasher;
+
+  fn build_hasher(&self) -> XxHasher {
+    XxHasher {
+      seed: self.seed,
+      buf: Vec::new(),
+    }
+  }
+}
+
 /// A Bloom filter implementation that tracks the total number of set bits along
 /// with the underlying bit vector and hashing functions, Murmur3 and xxHash.
 pub struct BloomFilter<R: BuildHasher, S: BuildHasher> {
   bit_vec: BitVec,
   num_hashes: u64,
   set_bits: u64,
+  seed: u64,
+  journal: Option<Vec<u64>>,
   murmur_hasher: R,
   xx_hasher: S,
 }
 
-impl BloomFilter<RandomState<murmur3::Murmur3_x64_128>, RandomState<xx::XXHash64>> {
+impl BloomFilter<MurmurBuildHasher, XxBuildHasher> {
   /// Return a new Bloom filter with a given number of approximate items to set.
   /// The default false positive probability is set and defined by DEFAULT_FALSE_POS.
   pub fn new(approx_items: u64) -> Self {
@@ -53,6 +194,19 @@ impl BloomFilter<RandomState<murmur3::Murmur3_x64_128>, RandomState<xx::XXHash64
   /// Return a new Bloom filter with a given number of approximate items to set
   /// and a desired false positive probability.
   pub fn new_with_rate(approx_items: u64, fp_prob: f64) -> Self {
+    BloomFilter::new_with_seed(approx_items, fp_prob, rand::random::<u64>())
+  }
+
+  /// Return a new Bloom filter with a given number of approximate items to set,
+  /// a desired false positive probability, and a fixed seed for its Murmur3 and
+  /// xxHash hashers.
+  ///
+  /// Unlike `new`/`new_with_rate`, which seed their hashers randomly on every
+  /// call, two filters built with the same approx_items, fp_prob, and seed hash
+  /// every object identically. This allows a filter built on one node to be
+  /// rebuilt byte-for-byte on another, or a reproducible filter to be derived
+  /// from the same input set every time.
+  pub fn new_with_seed(approx_items: u64, fp_prob: f64, seed: u64) -> Self {
     let num_bits = optimal_num_bits(approx_items, fp_prob);
     let num_hashes = optimal_num_hashes(num_bits, approx_items);
 
@@ -60,9 +214,58 @@ impl BloomFilter<RandomState<murmur3::Murmur3_x64_128>, RandomState<xx::XXHash64
       bit_vec: BitVec::from_elem(num_bits as usize, UNSET_BIT),
       num_hashes: num_hashes,
       set_bits: 0,
-      murmur_hasher: RandomState::<murmur3::Murmur3_x64_128>::new(),
-      xx_hasher: RandomState::<xx::XXHash64>::new(),
+      seed: seed,
+      journal: None,
+      murmur_hasher: MurmurBuildHasher { seed: seed },
+      xx_hasher: XxBuildHasher { seed: seed },
+    }
+  }
+
+  /// Reconstruct a Bloom filter previously serialized with `to_bytes`.
+  ///
+  /// Returns a `DeserializeError` if `bytes` is truncated, does not carry the
+  /// expected magic header, or was written by an unsupported wire format
+  /// version.
+  pub fn from_bytes(bytes: &[u8]) -> Result<Self, DeserializeError> {
+    if bytes.len() < SERIALIZE_HEADER_LEN {
+      return Err(DeserializeError::UnexpectedEof);
+    }
+
+    if bytes[0..4] != SERIALIZE_MAGIC {
+      return Err(DeserializeError::InvalidMagic);
     }
+
+    let version = bytes[4];
+    if version != SERIALIZE_VERSION {
+      return Err(DeserializeError::UnsupportedVersion(version));
+    }
+
+    let seed = u64::from_le_bytes(bytes[5..13].try_into().unwrap());
+    let num_hashes = u64::from_le_bytes(bytes[13..21].try_into().unwrap());
+    let set_bits = u64::from_le_bytes(bytes[21..29].try_into().unwrap());
+    let bit_len = u64::from_le_bytes(bytes[29..37].try_into().unwrap());
+
+    // BitVec::from_bytes only consumes the bytes actually present and
+    // truncate can only shrink, so a truncated payload must be rejected here
+    // or it would silently yield a filter with the wrong bit length.
+    let body_len = bytes.len() - SERIALIZE_HEADER_LEN;
+    let expected_body_len = bit_len.div_ceil(8) as usize;
+    if body_len < expected_body_len {
+      return Err(DeserializeError::UnexpectedEof);
+    }
+
+    let mut bit_vec = BitVec::from_bytes(&bytes[SERIALIZE_HEADER_LEN..]);
+    bit_vec.truncate(bit_len as usize);
+
+    Ok(BloomFilter {
+      bit_vec: bit_vec,
+      num_hashes: num_hashes,
+      set_bits: set_bits,
+      seed: seed,
+      journal: None,
+      murmur_hasher: MurmurBuildHasher { seed: seed },
+      xx_hasher: XxBuildHasher { seed: seed },
+    })
   }
 }
 
@@ -91,6 +294,51 @@ where
       //
       // NOTE: We should not panic here as enhanced_double_hash ensures the
       // index is within bounds via modulo bit vector table size.
+      if self.bit_vec.get(bit_idx).unwrap() == UNSET_BIT {
+        self.set_bits += 1;
+
+        if let Some(journal) = self.journal.as_mut() {
+          journal.push(bit_idx as u64);
+        }
+      }
+
+      self.bit_vec.set(bit_idx, SET_BIT);
+    }
+  }
+
+  /// Enable journaling of bits flipped from 0 to 1 by `set`. Has no effect if
+  /// journaling is already enabled. Intended for filters backed by durable
+  /// storage: instead of rewriting the whole bit vector on every flush,
+  /// callers can periodically `drain_journal` and persist only the delta.
+  pub fn enable_journaling(&mut self) {
+    if self.journal.is_none() {
+      self.journal = Some(Vec::new());
+    }
+  }
+
+  /// Returns `true` if journaling is currently enabled for this filter.
+  pub fn is_journaling(&self) -> bool {
+    self.journal.is_some()
+  }
+
+  /// Remove and return the indices of bits flipped from 0 to 1 by `set` since
+  /// the last call to `drain_journal`, or since journaling was enabled.
+  /// Returns an empty vector if journaling is not enabled.
+  pub fn drain_journal(&mut self) -> Vec<u64> {
+    match self.journal.as_mut() {
+      Some(journal) => std::mem::take(journal),
+      None => Vec::new(),
+    }
+  }
+
+  /// Replay bit indices previously produced by `drain_journal` onto this
+  /// filter, flipping each to 1 and updating `set_bits` accordingly. This
+  /// lets a follower instance stay in sync with only the delta since its
+  /// last checkpoint, rather than the full bit vector.
+  pub fn apply_journal(&mut self, indices: &[u64]) {
+    for &bit_idx in indices {
+      let bit_idx = bit_idx as usize;
+
       if self.bit_vec.get(bit_idx).unwrap() == UNSET_BIT {
         self.set_bits += 1;
       }
@@ -137,9 +385,72 @@ where
     (-(m / k) * (1.0 - (x / m)).ln()) as u64
   }
 
+  /// Serialize this Bloom filter to a versioned byte payload suitable for
+  /// persistence or transmission to another process. The payload carries the
+  /// seed used to build this filter's hashers, so `BloomFilter::from_bytes`
+  /// can reconstruct a filter that hashes identically to this one.
+  pub fn to_bytes(&self) -> Vec<u8> {
+    let bit_vec_bytes = self.bit_vec.to_bytes();
+    let mut buf = Vec::with_capacity(SERIALIZE_HEADER_LEN + bit_vec_bytes.len());
+
+    buf.extend_from_slice(&SERIALIZE_MAGIC);
+    buf.push(SERIALIZE_VERSION);
+    buf.extend_from_slice(&self.seed.to_le_bytes());
+    buf.extend_from_slice(&self.num_hashes.to_le_bytes());
+    buf.extend_from_slice(&self.set_bits.to_le_bytes());
+    buf.extend_from_slice(&(self.bit_vec.len() as u64).to_le_bytes());
+    buf.extend_from_slice(&bit_vec_bytes);
+
+    buf
+  }
+
+  /// Merge `other` into this filter in place so that it reports "possibly in
+  /// set" for any object either filter reported as such. Both filters must
+  /// share the same bit length, number of hashes, and seed, since mixing
+  /// incompatible parameters would silently break membership guarantees.
+  pub fn union(&mut self, other: &Self) -> Result<(), IncompatibleFilterError> {
+    self.check_compatible(other)?;
+    self.bit_vec.or(&other.bit_vec);
+    self.recount_set_bits();
+    Ok(())
+  }
+
+  /// Intersect this filter with `other` in place so that it reports
+  /// "possibly in set" only for objects both filters reported as such. Both
+  /// filters must share the same bit length, number of hashes, and seed,
+  /// since mixing incompatible parameters would silently break membership
+  /// guarantees.
+  pub fn intersect(&mut self, other: &Self) -> Result<(), IncompatibleFilterError> {
+    self.check_compatible(other)?;
+    self.bit_vec.and(&other.bit_vec);
+    self.recount_set_bits();
+    Ok(())
+  }
+
+  fn check_compatible(&self, other: &Self) -> Result<(), IncompatibleFilterError> {
+    if self.bit_vec.len() != other.bit_vec.len()
+      || self.num_hashes != other.num_hashes
+      || self.seed != other.seed
+    {
+      return Err(IncompatibleFilterError);
+    }
+
+    Ok(())
+  }
+
+  fn recount_set_bits(&mut self) {
+    self.set_bits = self.bit_vec.iter().filter(|bit| *bit).count() as u64;
+  }
+
+  /// Returns the fraction of bits currently set. Used internally by
+  /// `ScalableBloomFilter` to decide when a stage has filled up enough that
+  /// a new, larger stage should be started.
+  pub(crate) fn fill_ratio(&self) -> f64 {
+    self.set_bits as f64 / self.bit_vec.len() as f64
+  }
+
   fn enhanced_double_hash(&self, h1: u64, h2: u64, i: u64) -> u64 {
-    let r = h1.wrapping_add(i.wrapping_mul(h2)).wrapping_add(i.pow(3));
-    r % self.bit_vec.len() as u64
+    unbiased_bit_index(h1, h2, i, self.bit_vec.len() as u64)
   }
 }
 
@@ -155,6 +466,248 @@ fn optimal_num_hashes(num_bits: u64, approx_items: u64) -> u64 {
   (((num_bits / approx_items) as f64) * core::f64::consts::LN_2).ceil() as u64
 }
 
+/// Reduce a 64-bit enhanced double hash to an unbiased index in `0..m`.
+///
+/// Plain `r % m` is biased whenever `m` does not evenly divide 2^64: the low
+/// indices end up with slightly more probability mass, which degrades the
+/// real false-positive rate versus the theoretical one `fp_prob` promises.
+/// When `m` is a power of two the bias is zero (reduction is an exact
+/// bitmask), so that case is handled directly. Otherwise this applies
+/// rejection sampling: `threshold` is the largest multiple of `m` not
+/// exceeding 2^64, and any `r` landing at or above it is discarded in favor
+/// of a recomputed hash, guaranteeing every surviving index is equally
+/// likely. Rejection triggers rarely and costs nothing extra when `m` is a
+/// power of two.
+fn unbiased_bit_index(h1: u64, h2: u64, i: u64, m: u64) -> u64 {
+  if m.is_power_of_two() {
+    let r = h1.wrapping_add(i.wrapping_mul(h2)).wrapping_add(i.pow(3));
+    return r & (m - 1);
+  }
+
+  let threshold = ((1u128 << 64) / m as u128 * m as u128) as u64;
+  let mut j = i;
+
+  loop {
+    let r = h1.wrapping_add(j.wrapping_mul(h2)).wrapping_add(j.pow(3));
+
+    if r < threshold {
+      return r % m;
+    }
+
+    j += 1;
+  }
+}
+
+/// A Bloom filter variant that supports deletion by replacing each bit with a
+/// small saturating counter. This mirrors the counting-filter design used for
+/// ancestor filters in Servo's selector matching, at the cost of one byte per
+/// position instead of one bit.
+///
+/// Deleting an item that was never inserted can decrement counters shared with
+/// other items, which can introduce false negatives. Callers must only remove
+/// items they previously inserted.
+pub struct CountingBloomFilter<R: BuildHasher, S: BuildHasher> {
+  counters: Vec<u8>,
+  num_hashes: u64,
+  murmur_hasher: R,
+  xx_hasher: S,
+}
+
+impl CountingBloomFilter<MurmurBuildHasher, XxBuildHasher> {
+  /// Return a new counting Bloom filter with a given number of approximate
+  /// items to set. The default false positive probability is set and defined
+  /// by DEFAULT_FALSE_POS.
+  pub fn new(approx_items: u64) -> Self {
+    CountingBloomFilter::new_with_rate(approx_items, DEFAULT_FALSE_POS)
+  }
+
+  /// Return a new counting Bloom filter with a given number of approximate
+  /// items to set and a desired false positive probability.
+  pub fn new_with_rate(approx_items: u64, fp_prob: f64) -> Self {
+    let num_bits = optimal_num_bits(approx_items, fp_prob);
+    let num_hashes = optimal_num_hashes(num_bits, approx_items);
+    let seed = rand::random::<u64>();
+
+    CountingBloomFilter {
+      counters: vec![0u8; num_bits as usize],
+      num_hashes: num_hashes,
+      murmur_hasher: MurmurBuildHasher { seed: seed },
+      xx_hasher: XxBuildHasher { seed: seed },
+    }
+  }
+}
+
+impl<R, S> CountingBloomFilter<R, S>
+where
+  R: BuildHasher,
+  S: BuildHasher,
+{
+  /// Set an object in the counting Bloom filter. Each object must implement
+  /// the Hash trait. Each of the `k` counters touched by the object is
+  /// incremented, saturating at 255 so a hot counter cannot wrap around and
+  /// corrupt the structure.
+  pub fn set<T: Hash>(&mut self, obj: &T) {
+    for bit_idx in self.bit_indices(obj) {
+      let counter = &mut self.counters[bit_idx];
+      *counter = counter.saturating_add(1);
+    }
+  }
+
+  /// Remove an object from the counting Bloom filter. Each of the `k`
+  /// counters touched by the object is decremented, saturating at 0.
+  ///
+  /// Removing an item that was never inserted can decrement counters shared
+  /// with other items, introducing false negatives. Only remove items that
+  /// were previously set.
+  pub fn unset<T: Hash>(&mut self, obj: &T) {
+    for bit_idx in self.bit_indices(obj) {
+      let counter = &mut self.counters[bit_idx];
+      *counter = counter.saturating_sub(1);
+    }
+  }
+
+  /// Returns a bool reflecting if a given object is 'most likely' in the
+  /// counting Bloom filter or not. Membership holds when all `k` counters
+  /// touched by the object are greater than zero.
+  pub fn has<T: Hash>(&self, obj: &T) -> Option<bool> {
+    for bit_idx in self.bit_indices(obj) {
+      if self.counters[bit_idx] == 0 {
+        return Some(false);
+      }
+    }
+
+    Some(true)
+  }
+
+  fn bit_indices<T: Hash>(&self, obj: &T) -> Vec<usize> {
+    let mut hasher_one = self.murmur_hasher.build_hasher();
+    let mut hasher_two = self.xx_hasher.build_hasher();
+
+    obj.hash(&mut hasher_one);
+    obj.hash(&mut hasher_two);
+
+    let h1 = hasher_one.finish();
+    let h2 = hasher_two.finish();
+
+    (0..self.num_hashes)
+      .map(|i| self.enhanced_double_hash(h1, h2, i) as usize)
+      .collect()
+  }
+
+  fn enhanced_double_hash(&self, h1: u64, h2: u64, i: u64) -> u64 {
+    unbiased_bit_index(h1, h2, i, self.counters.len() as u64)
+  }
+}
+
+/// The fraction of a stage's bits that may be set before a new stage is
+/// started, rather than continuing to insert into an already-saturated one.
+const DEFAULT_SATURATION: f64 = 0.5;
+
+/// The factor by which each new stage's approximate item capacity grows over
+/// the previous stage.
+const DEFAULT_GROWTH_FACTOR: u64 = 2;
+
+/// The factor by which each new stage's false positive probability is
+/// tightened relative to the previous stage, so the compounded false
+/// positive probability across all stages converges rather than growing
+/// unboundedly.
+const DEFAULT_TIGHTENING_RATIO: f64 = 0.9;
+
+/// A Bloom filter that accepts unbounded insertions while keeping the
+/// aggregate false positive probability bounded, by growing into a new,
+/// larger stage whenever the current one fills up, rather than silently
+/// degrading past its original capacity like a plain `BloomFilter` does.
+///
+/// Each stage is an independent `BloomFilter` sized larger than the last
+/// (geometric capacity growth) with a tightened per-stage false positive
+/// probability, so the compounded false positive rate across all stages
+/// stays under the target passed to `new_with_rate`. `set` always writes to
+/// the newest stage; `has` reports membership if any stage does.
+pub struct ScalableBloomFilter {
+  stages: Vec<BloomFilter<MurmurBuildHasher, XxBuildHasher>>,
+  next_capacity: u64,
+  next_fp_prob: f64,
+}
+
+impl ScalableBloomFilter {
+  /// Return a new scalable Bloom filter whose first stage holds approximately
+  /// initial_capacity items. The default false positive probability is set
+  /// and defined by DEFAULT_FALSE_POS.
+  pub fn new(initial_capacity: u64) -> Self {
+    ScalableBloomFilter::new_with_rate(initial_capacity, DEFAULT_FALSE_POS)
+  }
+
+  /// Return a new scalable Bloom filter whose first stage holds approximately
+  /// initial_capacity items with a desired overall false positive
+  /// probability.
+  ///
+  /// The per-stage false positive probabilities form a geometric series with
+  /// ratio DEFAULT_TIGHTENING_RATIO, so stage 0 is sized to
+  /// `fp_prob * (1.0 - DEFAULT_TIGHTENING_RATIO)`: that makes the sum of the
+  /// whole (infinite) series converge to exactly `fp_prob`, which keeps the
+  /// compounded false positive rate across all stages under the target no
+  /// matter how many stages insertions eventually grow into.
+  pub fn new_with_rate(initial_capacity: u64, fp_prob: f64) -> Self {
+    let stage_fp_prob = fp_prob * (1.0 - DEFAULT_TIGHTENING_RATIO);
+    let first_stage = BloomFilter::new_with_rate(initial_capacity, stage_fp_prob);
+
+    ScalableBloomFilter {
+      stages: vec![first_stage],
+      next_capacity: initial_capacity * DEFAULT_GROWTH_FACTOR,
+      next_fp_prob: stage_fp_prob * DEFAULT_TIGHTENING_RATIO,
+    }
+  }
+
+  /// Set an object in the scalable Bloom filter. Each object must implement
+  /// the Hash trait. Writes always go to the newest stage, starting a new one
+  /// first if the current stage's fill ratio has crossed DEFAULT_SATURATION.
+  pub fn set<T: Hash>(&mut self, obj: &T) {
+    if self.current_stage().fill_ratio() >= DEFAULT_SATURATION {
+      self.grow();
+    }
+
+    self.current_stage_mut().set(obj);
+  }
+
+  /// Returns a bool reflecting if a given object is 'most likely' in the
+  /// scalable Bloom filter or not, i.e. if any stage reports it as set.
+  pub fn has<T: Hash>(&self, obj: &T) -> Option<bool> {
+    let found = self
+      .stages
+      .iter()
+      .any(|stage| stage.has(obj).unwrap_or(false));
+
+    Some(found)
+  }
+
+  /// Returns the approximate total number of objects set across all stages.
+  pub fn num_items_approx(&self) -> u64 {
+    self.stages.iter().map(|stage| stage.num_items_approx()).sum()
+  }
+
+  /// Returns the number of stages this filter has grown into so far.
+  pub fn stage_count(&self) -> usize {
+    self.stages.len()
+  }
+
+  fn grow(&mut self) {
+    let stage = BloomFilter::new_with_rate(self.next_capacity, self.next_fp_prob);
+
+    self.next_capacity *= DEFAULT_GROWTH_FACTOR;
+    self.next_fp_prob *= DEFAULT_TIGHTENING_RATIO;
+
+    self.stages.push(stage);
+  }
+
+  fn current_stage(&self) -> &BloomFilter<MurmurBuildHasher, XxBuildHasher> {
+    self.stages.last().unwrap()
+  }
+
+  fn current_stage_mut(&mut self) -> &mut BloomFilter<MurmurBuildHasher, XxBuildHasher> {
+    self.stages.last_mut().unwrap()
+  }
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
@@ -214,4 +767,233 @@ mod tests {
     assert_eq!(optimal_num_hashes(47926, 5000), 7);
     assert_eq!(optimal_num_hashes(958506, 100000), 7);
   }
+
+  #[test]
+  fn test_unbiased_bit_index_in_bounds() {
+    // power-of-two `m`: exercises the bitmask fast path.
+    for i in 0..16 {
+      assert!(unbiased_bit_index(123456789, 987654321, i, 64) < 64);
+    }
+
+    // non-power-of-two `m`: exercises the rejection sampling path.
+    for i in 0..16 {
+      assert!(unbiased_bit_index(123456789, 987654321, i, 67) < 67);
+    }
+  }
+
+  #[test]
+  fn test_counting_bloom_filter_set_and_has() {
+    let n = 1000;
+    let mut items = HashSet::<String>::new();
+
+    for _ in 0..n {
+      items.insert(random_str(30));
+    }
+
+    let mut cbf = CountingBloomFilter::new(items.len() as u64);
+
+    for item in items.iter() {
+      cbf.set(item);
+
+      let exists = cbf.has(item).unwrap();
+      assert_eq!(
+        exists, true,
+        "item {} should result in a positive inclusion",
+        item,
+      );
+    }
+  }
+
+  #[test]
+  fn test_counting_bloom_filter_unset() {
+    let mut cbf = CountingBloomFilter::new(100);
+
+    cbf.set(&"foo");
+    assert_eq!(cbf.has(&"foo").unwrap(), true);
+
+    cbf.unset(&"foo");
+    assert_eq!(cbf.has(&"foo").unwrap(), false);
+  }
+
+  #[test]
+  fn test_counting_bloom_filter_unset_saturates_at_zero() {
+    let mut cbf = CountingBloomFilter::new(100);
+
+    // unsetting an item that was never set should not underflow the counters
+    cbf.unset(&"foo");
+    cbf.unset(&"foo");
+
+    cbf.set(&"foo");
+    assert_eq!(cbf.has(&"foo").unwrap(), true);
+  }
+
+  #[test]
+  fn test_new_with_seed_is_deterministic() {
+    let mut bf1 = BloomFilter::new_with_seed(1000, DEFAULT_FALSE_POS, 42);
+    let mut bf2 = BloomFilter::new_with_seed(1000, DEFAULT_FALSE_POS, 42);
+
+    bf1.set(&"foo");
+    bf2.set(&"foo");
+
+    assert_eq!(bf1.to_bytes(), bf2.to_bytes());
+  }
+
+  #[test]
+  fn test_to_bytes_from_bytes_round_trip() {
+    let mut bf = BloomFilter::new_with_seed(1000, DEFAULT_FALSE_POS, 42);
+
+    bf.set(&"foo");
+    bf.set(&"bar");
+
+    let bytes = bf.to_bytes();
+    let restored = BloomFilter::from_bytes(&bytes).unwrap();
+
+    assert_eq!(restored.has(&"foo").unwrap(), true);
+    assert_eq!(restored.has(&"bar").unwrap(), true);
+    assert_eq!(restored.has(&"baz").unwrap(), false);
+    assert_eq!(restored.num_items_approx(), bf.num_items_approx());
+    assert_eq!(restored.to_bytes(), bytes);
+  }
+
+  #[test]
+  fn test_from_bytes_rejects_bad_input() {
+    assert_eq!(
+      BloomFilter::from_bytes(&[0u8; 4]).err(),
+      Some(DeserializeError::UnexpectedEof),
+    );
+
+    let bad_magic = vec![0u8; SERIALIZE_HEADER_LEN];
+    assert_eq!(
+      BloomFilter::from_bytes(&bad_magic).err(),
+      Some(DeserializeError::InvalidMagic),
+    );
+
+    let mut bad_version = BloomFilter::new(100).to_bytes();
+    bad_version[4] = 255;
+    assert_eq!(
+      BloomFilter::from_bytes(&bad_version).err(),
+      Some(DeserializeError::UnsupportedVersion(255)),
+    );
+
+    let truncated = &BloomFilter::new(100).to_bytes()[..SERIALIZE_HEADER_LEN + 1];
+    assert_eq!(
+      BloomFilter::from_bytes(truncated).err(),
+      Some(DeserializeError::UnexpectedEof),
+    );
+  }
+
+  #[test]
+  fn test_union() {
+    let mut bf1 = BloomFilter::new_with_seed(1000, DEFAULT_FALSE_POS, 42);
+    let mut bf2 = BloomFilter::new_with_seed(1000, DEFAULT_FALSE_POS, 42);
+
+    bf1.set(&"foo");
+    bf2.set(&"bar");
+
+    bf1.union(&bf2).unwrap();
+
+    assert_eq!(bf1.has(&"foo").unwrap(), true);
+    assert_eq!(bf1.has(&"bar").unwrap(), true);
+  }
+
+  #[test]
+  fn test_intersect() {
+    let mut bf1 = BloomFilter::new_with_seed(1000, DEFAULT_FALSE_POS, 42);
+    let mut bf2 = BloomFilter::new_with_seed(1000, DEFAULT_FALSE_POS, 42);
+
+    bf1.set(&"foo");
+    bf1.set(&"bar");
+    bf2.set(&"bar");
+
+    bf1.intersect(&bf2).unwrap();
+
+    assert_eq!(bf1.has(&"foo").unwrap(), false);
+    assert_eq!(bf1.has(&"bar").unwrap(), true);
+  }
+
+  #[test]
+  fn test_union_rejects_incompatible_filters() {
+    let mut bf1 = BloomFilter::new_with_seed(1000, DEFAULT_FALSE_POS, 42);
+    let bf2 = BloomFilter::new_with_seed(1000, DEFAULT_FALSE_POS, 7);
+
+    assert_eq!(
+      bf1.union(&bf2).unwrap_err(),
+      IncompatibleFilterError,
+    );
+  }
+
+  #[test]
+  fn test_journaling_disabled_by_default() {
+    let mut bf = BloomFilter::new(100);
+
+    assert_eq!(bf.is_journaling(), false);
+
+    bf.set(&"foo");
+    assert_eq!(bf.drain_journal(), Vec::<u64>::new());
+  }
+
+  #[test]
+  fn test_drain_and_apply_journal() {
+    let mut bf = BloomFilter::new_with_seed(1000, DEFAULT_FALSE_POS, 42);
+    bf.enable_journaling();
+    assert_eq!(bf.is_journaling(), true);
+
+    bf.set(&"foo");
+    bf.set(&"bar");
+
+    let journal = bf.drain_journal();
+    assert!(!journal.is_empty());
+    assert_eq!(bf.drain_journal(), Vec::<u64>::new());
+
+    let mut replica = BloomFilter::new_with_seed(1000, DEFAULT_FALSE_POS, 42);
+    replica.apply_journal(&journal);
+
+    assert_eq!(replica.has(&"foo").unwrap(), true);
+    assert_eq!(replica.has(&"bar").unwrap(), true);
+    assert_eq!(replica.num_items_approx(), bf.num_items_approx());
+  }
+
+  #[test]
+  fn test_scalable_bloom_filter_set_and_has() {
+    let n = 1000;
+    let mut items = HashSet::<String>::new();
+
+    for _ in 0..n {
+      items.insert(random_str(30));
+    }
+
+    let mut sbf = ScalableBloomFilter::new(items.len() as u64);
+
+    for item in items.iter() {
+      sbf.set(item);
+
+      let exists = sbf.has(item).unwrap();
+      assert_eq!(
+        exists, true,
+        "item {} should result in a positive inclusion",
+        item,
+      );
+    }
+  }
+
+  #[test]
+  fn test_scalable_bloom_filter_grows_past_initial_capacity() {
+    let mut sbf = ScalableBloomFilter::new_with_rate(10, DEFAULT_FALSE_POS);
+    assert_eq!(sbf.stage_count(), 1);
+
+    let mut items = HashSet::<String>::new();
+    for _ in 0..1000 {
+      items.insert(random_str(30));
+    }
+
+    for item in items.iter() {
+      sbf.set(item);
+    }
+
+    assert!(sbf.stage_count() > 1);
+
+    for item in items.iter() {
+      assert_eq!(sbf.has(item).unwrap(), true);
+    }
+  }
 }
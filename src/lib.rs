@@ -80,4 +80,7 @@
 pub mod bloom;
 
 // re-export library modules
-pub use self::bloom::BloomFilter;
+pub use self::bloom::{
+  BloomFilter, CountingBloomFilter, DeserializeError, IncompatibleFilterError, MurmurBuildHasher,
+  ScalableBloomFilter, XxBuildHasher,
+};